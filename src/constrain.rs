@@ -0,0 +1,130 @@
+use std::fmt::Display;
+use rand::Rng;
+use rand::distributions::Distribution;
+use rand::distributions::uniform::{Uniform, SampleUniform};
+
+#[derive(Debug, Clone)]
+pub enum Range<T> where T: SampleUniform + PartialOrd + Display + Copy {
+    Exact(T),
+    Constraint { min: T, max: T }, // inclusive for the moment
+}
+
+impl<T> Range<T> where T: SampleUniform + PartialOrd + Display + Copy {
+    pub fn min(&mut self, new_min: T) -> Result<(), String> {
+        match self {
+            Range::Exact(val) if new_min > *val
+                => Err(format!("No possible value: new minimum value ({}) greater than exact value ({})", new_min, val))?,
+            Range::Exact(_)
+                => {},
+            Range::Constraint { max, .. } if new_min > *max
+                => Err(format!("No possible value: new minimum value ({}) greater than maximum value ({})", new_min, max))?,
+            Range::Constraint { min, .. } if new_min > *min
+                => *min = new_min,
+            Range::Constraint { .. }
+                => {},
+        }
+
+        Ok(())
+    }
+
+    pub fn max(&mut self, new_max: T) -> Result<(), String> {
+        match self {
+            Range::Exact(val) if new_max < *val
+                => Err(format!("No possible value: new maximum value ({}) smaller than exact value ({})", new_max, val))?,
+            Range::Exact(_)
+                => {},
+            Range::Constraint { min, .. } if new_max < *min
+                => Err(format!("No possible value: new maximum value ({}) smaller than minimum value ({})", new_max, min))?,
+            Range::Constraint { max, .. } if new_max < *max
+                => *max = new_max,
+            Range::Constraint { .. }
+                => {},
+        }
+
+        Ok(())
+    }
+
+    // convienence wrapper around min+max at same time
+    pub fn constrain(&mut self, new_min: T, new_max: T) -> Result<(), String> {
+        self.min(new_min)?;
+        self.max(new_max)
+    }
+
+    pub fn value(&mut self, new_value: T) -> Result<(), String> {
+        match self {
+            Range::Exact(val) if *val != new_value
+                => Err(format!("No possible value: constrained to two different exact values. old: {}, new: {}", val, new_value))?,
+            Range::Exact(_)
+                => {},
+            Range::Constraint { min, .. } if new_value < *min
+                => Err(format!("No possible value: new exact value ({}) smaller than minimum value ({})", new_value, min))?,
+            Range::Constraint { max, .. } if new_value > *max
+                => Err(format!("No possible value: new exact value ({}) greater than maximum value ({})", new_value, max))?,
+            Range::Constraint { .. }
+                => *self = Range::Exact(new_value)
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Distribution<T> for Range<T> where T: SampleUniform + PartialOrd + Display + Copy {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        match self {
+            Range::Exact(val) => *val,
+            Range::Constraint { min, max } => rng.sample(Uniform::new_inclusive(min, max))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Set<T>(pub std::collections::BTreeSet<T>);
+
+impl<T> Set<T> where T: Ord + Display + Clone {
+    pub fn new(elems: &[T]) -> Result<Self, String> {
+        if elems.is_empty() {
+            return Err("Cannot construct a Set from an empty list of values".to_string());
+        }
+
+        Ok(Set(elems.iter().cloned().collect()))
+    }
+
+    pub fn remove(&mut self, elem: &T) -> Result<(), String> {
+        self.0.remove(elem);
+        if self.0.is_empty() {
+            Err(format!("Removed last element from set: {}", elem))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn subtract(&mut self, elems: &[T]) -> Result<(), String> {
+        for elem in elems {
+            self.remove(elem)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn intersect(&mut self, elems: &[T]) -> Result<(), String> {
+        self.0 = self.0.intersection(&elems.iter().cloned().collect()).cloned().collect();
+        if self.0.is_empty() {
+            Err("No items left in set!".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T> Distribution<T> for Set<T> where T: Ord + Clone {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        if self.0.is_empty() {
+            panic!("Attempted to sample an empty set!");
+        }
+
+        let idx = rng.gen_range(0, self.0.len());
+
+        // take the item at the randomly generated index in the BTreeSet
+        self.0.iter().nth(idx).unwrap().clone()
+    }
+}
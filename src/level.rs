@@ -0,0 +1,173 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{Cursor, Read, Write};
+use serde::{Serialize, Deserialize};
+use binread::{BinRead, BinResult, ReadOptions};
+use binwrite::{BinWrite, WriterOption};
+
+const RECORD_SIZE: usize = 64;
+const EXPECTED_COUNT: usize = 99;
+
+// thin wrapper giving the on-disk u32 a human-readable JSON representation, with an `Unknown`
+// fallback for every value we haven't identified yet.
+macro_rules! u32_enum {
+    ($name:ident { $($variant:ident = $value:expr),* $(,)? }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        pub enum $name {
+            $($variant,)*
+            Unknown(u32),
+        }
+
+        impl From<u32> for $name {
+            fn from(value: u32) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Unknown(other),
+                }
+            }
+        }
+
+        impl From<$name> for u32 {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(raw) => raw,
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name::from(0)
+            }
+        }
+
+        impl BinRead for $name {
+            type Args = ();
+
+            fn read_options<R: binread::io::Read + binread::io::Seek>(reader: &mut R, options: &ReadOptions, _args: Self::Args) -> BinResult<Self> {
+                let raw = u32::read_options(reader, options, ())?;
+                Ok($name::from(raw))
+            }
+        }
+
+        impl BinWrite for $name {
+            fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> std::io::Result<()> {
+                u32::from(*self).write_options(writer, options)
+            }
+        }
+    };
+}
+
+u32_enum!(LevelType {
+    PickYourFavorite = 6,
+    FindYourFavorite = 7,
+});
+
+// behavior names are our best guess at what the game's loader means by these values, inferred
+// from which ones the objective checks in validate.rs require -- not confirmed against the game itself.
+u32_enum!(Behavior {
+    Idle = 0,
+    Posing = 1,
+    Wandering = 4,
+});
+
+u32_enum!(Map {
+    FortyMiis = 4,
+});
+
+#[derive(Serialize, Deserialize, BinRead, BinWrite, Default, Debug, Clone)]
+#[br(big)]
+#[binwrite(big)]
+pub struct Level {
+    pub num_miis: u32,
+    pub behavior: Behavior,
+    pub level_type: LevelType,
+    pub map: Map,
+    pub zoom_out_max: f32,
+    pub zoom_in_max: f32,
+    pub unk7: f32,
+    pub horiz_dist: f32,
+    pub vert_dist: f32,
+    pub darkness: f32,
+    pub head_size: f32,
+    pub unk12: f32,
+    pub unk13: f32,
+    pub unk14: f32,
+    pub unk15: f32,
+    pub unk16: f32
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Read(std::io::Error),
+    Write(std::io::Error),
+    // the file ended partway through a level record instead of cleanly between two records
+    Truncated { level_index: usize, byte_offset: u64, bytes_read: usize },
+    Parse { level_index: usize, byte_offset: u64, source: binread::Error },
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LevelError::Read(e) => write!(f, "failed to read level file: {}", e),
+            LevelError::Write(e) => write!(f, "failed to write level file: {}", e),
+            LevelError::Truncated { level_index, byte_offset, bytes_read } =>
+                write!(f, "file is truncated: level index {} starts at byte offset {} but only {} of {} expected bytes are present",
+                    level_index, byte_offset, bytes_read, RECORD_SIZE),
+            LevelError::Parse { level_index, byte_offset, source } =>
+                write!(f, "failed to parse level index {} at byte offset {}: {}", level_index, byte_offset, source),
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
+
+impl Level {
+    // reads levels until EOF instead of assuming exactly 99 are present, so a file that ends
+    // partway through a level fails with a Truncated error naming that level instead of an opaque
+    // binread panic, and a file with more or fewer than 99 whole levels is read in full with a
+    // warning rather than having its tail dropped.
+    pub fn from_file(mut input: File) -> Result<Vec<Level>, LevelError> {
+        let mut levels = Vec::new();
+        let mut buf = [0u8; RECORD_SIZE];
+        let mut offset: u64 = 0;
+
+        loop {
+            let mut filled = 0;
+            while filled < RECORD_SIZE {
+                let read = input.read(&mut buf[filled..]).map_err(LevelError::Read)?;
+                if read == 0 {
+                    break;
+                }
+                filled += read;
+            }
+
+            if filled == 0 {
+                break; // clean end of file between two levels
+            }
+
+            if filled < RECORD_SIZE {
+                return Err(LevelError::Truncated { level_index: levels.len(), byte_offset: offset, bytes_read: filled });
+            }
+
+            let mut cursor = Cursor::new(&buf[..]);
+            let level = Level::read(&mut cursor)
+                .map_err(|source| LevelError::Parse { level_index: levels.len(), byte_offset: offset, source })?;
+
+            levels.push(level);
+            offset += RECORD_SIZE as u64;
+        }
+
+        if levels.len() != EXPECTED_COUNT {
+            eprintln!("Warning: expected {} levels but found {} in the input file.", EXPECTED_COUNT, levels.len());
+        }
+
+        Ok(levels)
+    }
+
+    pub fn to_file(mut output: File, levels: Vec<Level>) -> Result<(), LevelError> {
+        levels.write(&mut output).map_err(LevelError::Write)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod level;
+pub mod constrain;
+pub mod randomize;
+pub mod validate;
+pub mod minimize;
+
+pub use level::Level;
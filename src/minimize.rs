@@ -0,0 +1,164 @@
+use crate::level::{Level, LevelType, Behavior, Map};
+use crate::validate::{find_warnings, WarningCategory};
+
+pub struct MinimizeReport {
+    pub levels: Vec<Level>,
+    // index into the *original* input for every level that's still non-default in the output,
+    // in output order, so a bug report can say "this was originally level 42".
+    pub original_indices: Vec<usize>,
+}
+
+fn is_default(level: &Level) -> bool {
+    level.num_miis == 0 && level.behavior == Behavior::default() && level.level_type == LevelType::default() && level.map == Map::default()
+        && level.zoom_out_max == 0.0 && level.zoom_in_max == 0.0 && level.unk7 == 0.0
+        && level.horiz_dist == 0.0 && level.vert_dist == 0.0 && level.darkness == 0.0
+        && level.head_size == 0.0 && level.unk12 == 0.0 && level.unk13 == 0.0
+        && level.unk14 == 0.0 && level.unk15 == 0.0 && level.unk16 == 0.0
+}
+
+fn still_flagged(levels: &[Level], category: WarningCategory) -> bool {
+    find_warnings(levels).iter().any(|warning| warning.category() == category)
+}
+
+// delta-debugging-style shrink: finds the smallest set of non-default levels that still trips
+// the same warning category the input originally tripped. Returns None if the input doesn't
+// trip anything to begin with.
+pub fn minimize(mut levels: Vec<Level>) -> Option<MinimizeReport> {
+    let category = find_warnings(&levels).first()?.category();
+
+    reset_levels_to_default(&mut levels, category);
+    lower_numeric_fields(&mut levels, category);
+
+    let original_indices = (0..levels.len()).filter(|&i| !is_default(&levels[i])).collect();
+
+    Some(MinimizeReport { levels, original_indices })
+}
+
+// round 1: ddmin over whole levels. Repeatedly try resetting chunks of levels to
+// Level::default(), halving the chunk size each pass (all, 1/2, 1/4, ..., 1 level at a time), and
+// keep any reset that preserves the target warning category. Repeats to a fixpoint, since
+// resetting one chunk can make a previously-load-bearing chunk safe to reset too.
+fn reset_levels_to_default(levels: &mut [Level], category: WarningCategory) {
+    loop {
+        let mut changed = false;
+        let mut chunk_size = levels.len().max(1);
+
+        loop {
+            let mut start = 0;
+            while start < levels.len() {
+                let end = (start + chunk_size).min(levels.len());
+
+                if (start..end).any(|i| !is_default(&levels[i])) {
+                    let backup: Vec<Level> = levels[start..end].to_vec();
+                    for level in &mut levels[start..end] {
+                        *level = Level::default();
+                    }
+
+                    if still_flagged(levels, category) {
+                        changed = true;
+                    } else {
+                        levels[start..end].clone_from_slice(&backup);
+                    }
+                }
+
+                start = end;
+            }
+
+            if chunk_size == 1 {
+                break;
+            }
+            chunk_size /= 2;
+        }
+
+        if !changed {
+            break;
+        }
+    }
+}
+
+macro_rules! bisect_fields {
+    ($levels:expr, $category:expr, u32: [$($u_field:ident),*], enum32: [$($e_field:ident: $e_ty:ty),*], f32: [$($f_field:ident),*]) => {
+        for idx in 0..$levels.len() {
+            $(bisect_u32($levels, idx, $category, |l| l.$u_field, |l, v| l.$u_field = v);)*
+            $(bisect_u32($levels, idx, $category, |l| u32::from(l.$e_field), |l, v| l.$e_field = <$e_ty>::from(v));)*
+            $(bisect_f32($levels, idx, $category, |l| l.$f_field, |l, v| l.$f_field = v);)*
+        }
+    };
+}
+
+// round 2: for every level still left over after round 1, bisect each numeric field towards its
+// default value (0 / 0.0), keeping any lowering that preserves the target warning category.
+// Bisection finds the smallest flagged value only where the predicate is monotonic in the field,
+// which holds for the num_miis check alone; the level_type, behavior and map checks match
+// specific values, so for those fields the kept value is one that still trips the category but
+// not necessarily the smallest such value.
+fn lower_numeric_fields(levels: &mut [Level], category: WarningCategory) {
+    bisect_fields!(
+        levels, category,
+        u32: [num_miis],
+        enum32: [behavior: Behavior, level_type: LevelType, map: Map],
+        f32: [zoom_out_max, zoom_in_max, unk7, horiz_dist, vert_dist, darkness, head_size, unk12, unk13, unk14, unk15, unk16]
+    );
+}
+
+fn bisect_u32(
+    levels: &mut [Level],
+    idx: usize,
+    category: WarningCategory,
+    get: impl Fn(&Level) -> u32,
+    set: impl Fn(&mut Level, u32),
+) {
+    let original = get(&levels[idx]);
+    if original == 0 {
+        return;
+    }
+
+    set(&mut levels[idx], 0);
+    if still_flagged(levels, category) {
+        return;
+    }
+
+    let mut low = 0u32;
+    let mut high = original;
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        set(&mut levels[idx], mid);
+        if still_flagged(levels, category) {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    set(&mut levels[idx], high);
+}
+
+fn bisect_f32(
+    levels: &mut [Level],
+    idx: usize,
+    category: WarningCategory,
+    get: impl Fn(&Level) -> f32,
+    set: impl Fn(&mut Level, f32),
+) {
+    let original = get(&levels[idx]);
+    if original == 0.0 || !original.is_finite() {
+        return;
+    }
+
+    set(&mut levels[idx], 0.0);
+    if still_flagged(levels, category) {
+        return;
+    }
+
+    let mut low = 0.0f32;
+    let mut high = original;
+    for _ in 0..32 {
+        let mid = low + (high - low) / 2.0;
+        set(&mut levels[idx], mid);
+        if still_flagged(levels, category) {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    set(&mut levels[idx], high);
+}
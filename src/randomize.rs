@@ -0,0 +1,240 @@
+use std::path::Path;
+use rand::Rng;
+use rand::distributions::Distribution;
+use serde::Deserialize;
+
+use crate::constrain::{Range, Set};
+use crate::level::{Level, LevelType, Behavior, Map};
+
+// A sampler for one of the u32 fields: either a contiguous Range, or an explicit Set of
+// allowed values (needed once a constraint has to exclude values from the middle of a range,
+// e.g. the favorite-pairing level types, or the behavior values required by an objective).
+#[derive(Debug, Clone)]
+enum IntSampler {
+    Range(Range<u32>),
+    Set(Set<u32>),
+}
+
+impl IntSampler {
+    fn from_constraint(default_min: u32, default_max: u32, constraint: Option<&IntConstraint>) -> Result<Self, String> {
+        match constraint {
+            None => Ok(IntSampler::Range(Range::Constraint { min: default_min, max: default_max })),
+            Some(IntConstraint::Exact(value)) => Ok(IntSampler::Range(Range::Exact(*value))),
+            Some(IntConstraint::MinMax { min, max }) => {
+                let mut range = Range::Constraint { min: default_min, max: default_max };
+                range.constrain(*min, *max)?;
+                Ok(IntSampler::Range(range))
+            },
+            Some(IntConstraint::Set(values)) => Ok(IntSampler::Set(Set::new(values)?)),
+        }
+    }
+
+    fn sample(&self, rng: &mut impl Rng) -> u32 {
+        match self {
+            IntSampler::Range(range) => range.sample(rng),
+            IntSampler::Set(set) => set.sample(rng),
+        }
+    }
+
+    // narrow this sampler down to only the values present in `allowed`
+    fn constrain_to(&self, allowed: &[u32]) -> Result<Self, String> {
+        match self {
+            IntSampler::Range(Range::Exact(value)) if allowed.contains(value)
+                => Ok(IntSampler::Range(Range::Exact(*value))),
+            IntSampler::Range(Range::Exact(value))
+                => Err(format!("exact value {} is not one of the allowed values {:?}", value, allowed)),
+            IntSampler::Range(Range::Constraint { min, max }) => {
+                let remaining: Vec<u32> = allowed.iter().cloned().filter(|v| v >= min && v <= max).collect();
+                if remaining.is_empty() {
+                    Err(format!("no allowed value falls within the configured range ({}..={})", min, max))
+                } else {
+                    Ok(IntSampler::Set(Set::new(&remaining)?))
+                }
+            },
+            IntSampler::Set(set) => {
+                let mut set = set.clone();
+                set.intersect(allowed)?;
+                Ok(IntSampler::Set(set))
+            },
+        }
+    }
+
+    // narrow this sampler down to exclude every value present in `excluded`
+    fn exclude(&self, excluded: &[u32]) -> Result<Self, String> {
+        match self {
+            IntSampler::Range(Range::Exact(value)) if excluded.contains(value)
+                => Err(format!("exact value {} is one of the excluded values {:?}", value, excluded)),
+            IntSampler::Range(Range::Exact(value))
+                => Ok(IntSampler::Range(Range::Exact(*value))),
+            IntSampler::Range(Range::Constraint { min, max }) => {
+                let remaining: Vec<u32> = (*min..=*max).filter(|v| !excluded.contains(v)).collect();
+                if remaining.is_empty() {
+                    Err(format!("no value remains in the configured range ({}..={}) once {:?} are excluded", min, max, excluded))
+                } else {
+                    Ok(IntSampler::Set(Set::new(&remaining)?))
+                }
+            },
+            IntSampler::Set(set) => {
+                let mut set = set.clone();
+                set.subtract(excluded)?;
+                Ok(IntSampler::Set(set))
+            },
+        }
+    }
+
+    // cap the maximum value this sampler can produce, e.g. num_miis on a 40-mii map
+    fn cap_max(&self, new_max: u32) -> Result<Self, String> {
+        match self {
+            IntSampler::Range(range) => {
+                let mut range = range.clone();
+                range.max(new_max)?;
+                Ok(IntSampler::Range(range))
+            },
+            IntSampler::Set(set) => {
+                let remaining: Vec<u32> = set.0.iter().cloned().filter(|v| *v <= new_max).collect();
+                let mut set = set.clone();
+                set.intersect(&remaining)?;
+                Ok(IntSampler::Set(set))
+            },
+        }
+    }
+}
+
+fn float_range(default_min: f32, default_max: f32, constraint: Option<&FloatConstraint>) -> Result<Range<f32>, String> {
+    match constraint {
+        None => Ok(Range::Constraint { min: default_min, max: default_max }),
+        Some(FloatConstraint::Exact(value)) => Ok(Range::Exact(*value)),
+        Some(FloatConstraint::MinMax { min, max }) => {
+            let mut range = Range::Constraint { min: default_min, max: default_max };
+            range.constrain(*min, *max)?;
+            Ok(range)
+        },
+    }
+}
+
+// Set comes before MinMax: untagged MinMax would also accept a two-element list as [min, max].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum IntConstraint {
+    Exact(u32),
+    Set(Vec<u32>),
+    MinMax { min: u32, max: u32 },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum FloatConstraint {
+    Exact(f32),
+    MinMax { min: f32, max: f32 },
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Constraints {
+    num_miis: Option<IntConstraint>,
+    behavior: Option<IntConstraint>,
+    level_type: Option<IntConstraint>,
+    map: Option<IntConstraint>,
+    zoom_out_max: Option<FloatConstraint>,
+    zoom_in_max: Option<FloatConstraint>,
+    unk7: Option<FloatConstraint>,
+    horiz_dist: Option<FloatConstraint>,
+    vert_dist: Option<FloatConstraint>,
+    darkness: Option<FloatConstraint>,
+    head_size: Option<FloatConstraint>,
+    unk12: Option<FloatConstraint>,
+    unk13: Option<FloatConstraint>,
+    unk14: Option<FloatConstraint>,
+    unk15: Option<FloatConstraint>,
+    unk16: Option<FloatConstraint>,
+}
+
+pub fn load_constraints(path: &Path) -> Result<Constraints, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read constraints file {}: {}", path.display(), e))?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&text)
+            .map_err(|e| format!("failed to parse constraints file {} as TOML: {}", path.display(), e)),
+        _ => serde_json::from_str(&text)
+            .map_err(|e| format!("failed to parse constraints file {} as JSON: {}", path.display(), e)),
+    }
+}
+
+pub fn randomize(levels: &mut [Level], mut rng: impl Rng, constraints: &Constraints) -> Result<(), String> {
+    let base_level_type = IntSampler::from_constraint(1, 21, constraints.level_type.as_ref())?;
+    let base_behavior = IntSampler::from_constraint(0, 6, constraints.behavior.as_ref())?;
+    let base_num_miis = IntSampler::from_constraint(4, 90, constraints.num_miis.as_ref())?;
+    let base_map = IntSampler::from_constraint(0, 4, constraints.map.as_ref())?;
+
+    let zoom_out_max = float_range(-406.0, -135.0, constraints.zoom_out_max.as_ref())?;
+    let zoom_in_max = float_range(-135.0, -22.0, constraints.zoom_in_max.as_ref())?;
+    let darkness_range = float_range(38.0, 90.0, constraints.darkness.as_ref())?;
+    let head_size = float_range(1.35, 3.5, constraints.head_size.as_ref())?;
+
+    let mut favorite_pending = false;
+
+    let last_idx = levels.len() - 1;
+    for (idx, level) in levels.iter_mut().enumerate() {
+        let level_type_value = if idx == last_idx {
+            if favorite_pending {
+                favorite_pending = false;
+                7
+            } else {
+                // avoid generating 'pick your favorite'/'find your favorite' on the last level
+                let sampler = base_level_type.exclude(&[6, 7]).map_err(|e|
+                    format!("level index {}: level_type constraints leave no usable value: {}", idx, e))?;
+                sampler.sample(&mut rng)
+            }
+        } else {
+            let level_type = base_level_type.sample(&mut rng);
+            if level_type == 6 || level_type == 7 {
+                // special handling for levels dealing with favorites:
+                if favorite_pending {
+                    favorite_pending = false;
+                    7
+                } else {
+                    favorite_pending = true;
+                    6
+                }
+            } else {
+                level_type
+            }
+        };
+        level.level_type = LevelType::from(level_type_value);
+
+        let map_value = base_map.sample(&mut rng);
+
+        let num_miis_sampler = if map_value == 4 {
+            base_num_miis.cap_max(40).map_err(|e|
+                format!("level index {}: num_miis constraints leave no value <= 40 required by map 4: {}", idx, e))?
+        } else {
+            base_num_miis.clone()
+        };
+        level.num_miis = num_miis_sampler.sample(&mut rng);
+
+        let behavior_value = match level_type_value {
+            9..=11 => base_behavior.constrain_to(&[1, 4]).map_err(|e|
+                format!("level index {}: behavior constraints leave no value in {{1, 4}} required by objective type {}: {}", idx, level_type_value, e))?
+                .sample(&mut rng),
+            17..=19 => base_behavior.constrain_to(&[0]).map_err(|e|
+                format!("level index {}: behavior constraints leave no value of 0 required by objective type {}: {}", idx, level_type_value, e))?
+                .sample(&mut rng),
+            _ => base_behavior.sample(&mut rng),
+        };
+        level.behavior = Behavior::from(behavior_value);
+
+        level.map = Map::from(map_value);
+        level.zoom_out_max = zoom_out_max.sample(&mut rng);
+        level.zoom_in_max = zoom_in_max.sample(&mut rng);
+
+        level.darkness = if rng.gen_ratio(1, 2) {
+            0.0 // 50% chance for no darkness
+        } else {
+            darkness_range.sample(&mut rng)
+        };
+        level.head_size = head_size.sample(&mut rng);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use std::fmt;
+use crate::level::{Level, LevelType, Behavior, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningCategory {
+    UnmatchedFavoritePair,
+    BadObjectiveBehavior,
+    TooManyMiis,
+}
+
+#[derive(Debug, Clone)]
+pub enum Warning {
+    PickFavoriteAfterPickFavorite { index: usize },
+    FindFavoriteWithoutPickFavorite { index: usize },
+    TrailingPickFavorite,
+    BadObjectiveBehavior { index: usize, level_type: u32, behavior: u32 },
+    TooManyMiis { index: usize, num_miis: u32, max_miis: u32 },
+}
+
+impl Warning {
+    pub fn category(&self) -> WarningCategory {
+        match self {
+            Warning::PickFavoriteAfterPickFavorite { .. }
+                | Warning::FindFavoriteWithoutPickFavorite { .. }
+                | Warning::TrailingPickFavorite => WarningCategory::UnmatchedFavoritePair,
+            Warning::BadObjectiveBehavior { .. } => WarningCategory::BadObjectiveBehavior,
+            Warning::TooManyMiis { .. } => WarningCategory::TooManyMiis,
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::PickFavoriteAfterPickFavorite { index } =>
+                write!(f, "level index {} is of type 'pick your favorite' after another 'pick your favorite' level. Game will crash.", index),
+            Warning::FindFavoriteWithoutPickFavorite { index } =>
+                write!(f, "level index {} is of type 'find your favorite' without a preceeding 'pick your favorite' level. Game will crash.", index),
+            Warning::TrailingPickFavorite =>
+                write!(f, "there is no matching 'find your favorite' level to a 'pick your favorite' level, and we've reached the end of the file. Game will crash."),
+            Warning::BadObjectiveBehavior { index, level_type, behavior } if [9, 10, 11].contains(level_type) =>
+                write!(f, "level index {} has an objective that requires mii behaviors 1 or 4 to function properly, but is set to {}", index, behavior),
+            Warning::BadObjectiveBehavior { index, behavior, .. } =>
+                write!(f, "level index {} has an objective that requires mii behavior 0 to function properly, but is set to {}", index, behavior),
+            Warning::TooManyMiis { index, max_miis, .. } =>
+                write!(f, "level index {} has more than the maximum of {} miis for this map.", index, max_miis),
+        }
+    }
+}
+
+// Re-derives the crash-inducing conditions the game's loader is known to choke on: unmatched
+// 'pick your favorite'/'find your favorite' pairs, objectives paired with the wrong mii
+// behavior, and over-max mii counts for the selected map. Standalone so both Assemble and
+// Minimize can drive off of the same predicate.
+//
+// level_type 9/10/11 and 17/18/19 don't have confirmed names yet, so they're still matched as
+// raw numbers via LevelType::Unknown -- as more of the enum gets named, these can become typed
+// matches too.
+pub fn find_warnings(levels: &[Level]) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let mut favorite_pending = false;
+
+    for (idx, level) in levels.iter().enumerate() {
+        match level.level_type {
+            LevelType::PickYourFavorite if !favorite_pending => favorite_pending = true,
+            LevelType::PickYourFavorite => warnings.push(Warning::PickFavoriteAfterPickFavorite { index: idx }),
+            LevelType::FindYourFavorite if !favorite_pending => warnings.push(Warning::FindFavoriteWithoutPickFavorite { index: idx }),
+            LevelType::FindYourFavorite => favorite_pending = false,
+            LevelType::Unknown(9) | LevelType::Unknown(10) | LevelType::Unknown(11)
+                if !matches!(level.behavior, Behavior::Posing | Behavior::Wandering) =>
+                warnings.push(Warning::BadObjectiveBehavior {
+                    index: idx,
+                    level_type: u32::from(level.level_type),
+                    behavior: u32::from(level.behavior),
+                }),
+            LevelType::Unknown(17) | LevelType::Unknown(18) | LevelType::Unknown(19)
+                if level.behavior != Behavior::Idle =>
+                warnings.push(Warning::BadObjectiveBehavior {
+                    index: idx,
+                    level_type: u32::from(level.level_type),
+                    behavior: u32::from(level.behavior),
+                }),
+            _ => {}
+        }
+
+        let max_miis = match level.map {
+            Map::FortyMiis => 40,
+            _ => 99
+        };
+
+        if level.num_miis > max_miis {
+            warnings.push(Warning::TooManyMiis { index: idx, num_miis: level.num_miis, max_miis });
+        }
+    }
+
+    if favorite_pending {
+        warnings.push(Warning::TrailingPickFavorite);
+    }
+
+    warnings
+}
+
+pub fn is_flagged(levels: &[Level]) -> bool {
+    !find_warnings(levels).is_empty()
+}
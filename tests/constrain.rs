@@ -0,0 +1,108 @@
+// Unit-level tests for Range/Set's error paths, driven as integration tests since both types
+// are public. Exercises the constraint-narrowing operations randomize() relies on to reject
+// unsatisfiable configs with a Result instead of silently clamping or panicking.
+
+use find_mii_editor::constrain::{Range, Set};
+
+#[test]
+fn range_min_rejects_new_minimum_above_exact_value() {
+    let mut range = Range::Exact(5u32);
+    assert!(range.min(10).is_err());
+}
+
+#[test]
+fn range_min_rejects_new_minimum_above_max() {
+    let mut range = Range::Constraint { min: 0u32, max: 10 };
+    assert!(range.min(20).is_err());
+}
+
+#[test]
+fn range_min_raises_the_floor_when_possible() {
+    let mut range = Range::Constraint { min: 0u32, max: 10 };
+    range.min(5).expect("5 is within the existing range");
+    match range {
+        Range::Constraint { min, max } => {
+            assert_eq!(min, 5);
+            assert_eq!(max, 10);
+        },
+        Range::Exact(_) => panic!("min() on a Constraint should not produce an Exact"),
+    }
+}
+
+#[test]
+fn range_max_rejects_new_maximum_below_exact_value() {
+    let mut range = Range::Exact(5u32);
+    assert!(range.max(1).is_err());
+}
+
+#[test]
+fn range_max_rejects_new_maximum_below_min() {
+    let mut range = Range::Constraint { min: 10u32, max: 20 };
+    assert!(range.max(5).is_err());
+}
+
+#[test]
+fn range_constrain_rejects_an_inverted_bound() {
+    let mut range = Range::Constraint { min: 0u32, max: 90 };
+    assert!(range.constrain(50, 10).is_err());
+}
+
+#[test]
+fn range_value_rejects_a_second_incompatible_exact_value() {
+    let mut range = Range::Exact(5u32);
+    assert!(range.value(6).is_err());
+}
+
+#[test]
+fn range_value_rejects_a_value_outside_the_configured_bounds() {
+    let mut range = Range::Constraint { min: 0u32, max: 10 };
+    assert!(range.value(20).is_err());
+}
+
+#[test]
+fn range_value_narrows_a_constraint_down_to_exact() {
+    let mut range = Range::Constraint { min: 0u32, max: 10 };
+    range.value(5).expect("5 is within the configured bounds");
+    match range {
+        Range::Exact(value) => assert_eq!(value, 5),
+        Range::Constraint { .. } => panic!("value() should narrow a Constraint to an Exact"),
+    }
+}
+
+#[test]
+fn set_new_rejects_an_empty_list() {
+    let empty: Vec<u32> = Vec::new();
+    assert!(Set::new(&empty).is_err());
+}
+
+#[test]
+fn set_new_accepts_a_non_empty_list() {
+    assert!(Set::new(&[1u32, 2, 3]).is_ok());
+}
+
+#[test]
+fn set_remove_rejects_removing_the_last_element() {
+    let mut set = Set::new(&[1u32]).unwrap();
+    assert!(set.remove(&1).is_err());
+}
+
+#[test]
+fn set_subtract_rejects_removing_every_element() {
+    let mut set = Set::new(&[1u32, 2]).unwrap();
+    assert!(set.subtract(&[1, 2]).is_err());
+}
+
+#[test]
+fn set_intersect_rejects_an_empty_result() {
+    let mut set = Set::new(&[1u32, 2]).unwrap();
+    assert!(set.intersect(&[3, 4]).is_err());
+}
+
+#[test]
+fn set_intersect_keeps_the_common_elements() {
+    let mut set = Set::new(&[1u32, 2, 3]).unwrap();
+    set.intersect(&[2, 3, 4]).expect("2 and 3 are common to both lists");
+    assert_eq!(set.0.len(), 2);
+    assert!(set.0.contains(&2));
+    assert!(set.0.contains(&3));
+}
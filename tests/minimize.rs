@@ -0,0 +1,38 @@
+// Integration test for minimize(): feeds it a known crash-inducing set of levels and checks the
+// shrunk output still trips the same warning category while being smaller than the input.
+
+use find_mii_editor::level::{Level, Map};
+use find_mii_editor::minimize::minimize;
+use find_mii_editor::validate::{find_warnings, WarningCategory};
+
+const LEVEL_COUNT: usize = 99;
+const FLAGGED_INDEX: usize = 10;
+const MAX_MIIS_ON_FORTY_MII_MAP: u32 = 40;
+const ORIGINAL_NUM_MIIS: u32 = 50;
+
+fn crash_inducing_levels() -> Vec<Level> {
+    let mut levels = vec![Level::default(); LEVEL_COUNT];
+    levels[FLAGGED_INDEX].map = Map::FortyMiis;
+    levels[FLAGGED_INDEX].num_miis = ORIGINAL_NUM_MIIS;
+    levels
+}
+
+#[test]
+fn minimize_shrinks_too_many_miis_to_a_minimal_reproduction() {
+    let levels = crash_inducing_levels();
+    assert!(find_warnings(&levels).iter().any(|w| w.category() == WarningCategory::TooManyMiis));
+
+    let report = minimize(levels).expect("input should have been flagged");
+
+    assert_eq!(report.levels.len(), LEVEL_COUNT, "minimize must not change the level count");
+    assert_eq!(report.original_indices, vec![FLAGGED_INDEX], "every other level should shrink to default");
+
+    assert!(
+        find_warnings(&report.levels).iter().any(|w| w.category() == WarningCategory::TooManyMiis),
+        "shrunk output must still trip the original warning category"
+    );
+
+    let shrunk_num_miis = report.levels[FLAGGED_INDEX].num_miis;
+    assert!(shrunk_num_miis > MAX_MIIS_ON_FORTY_MII_MAP, "shrunk value must still exceed the max to stay flagged");
+    assert!(shrunk_num_miis < ORIGINAL_NUM_MIIS, "minimize should have lowered num_miis towards the max");
+}
@@ -0,0 +1,79 @@
+// Integration tests for randomize(): cross-field propagation (level_type -> behavior,
+// map -> num_miis) and the constraint error paths a hand-edited constraints file can trigger.
+
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256StarStar;
+
+use find_mii_editor::level::Level;
+use find_mii_editor::randomize::{randomize, Constraints};
+
+const LEVEL_COUNT: usize = 5;
+
+fn arbitrary_levels() -> Vec<Level> {
+    vec![Level::default(); LEVEL_COUNT]
+}
+
+fn constraints_from_json(json: &str) -> Constraints {
+    serde_json::from_str(json).expect("constraints JSON should deserialize")
+}
+
+#[test]
+fn objective_level_type_constrains_behavior_to_posing_or_wandering() {
+    let mut levels = arbitrary_levels();
+    let constraints = constraints_from_json(r#"{"level_type": 9}"#);
+    let rng = Xoshiro256StarStar::seed_from_u64(0);
+
+    randomize(&mut levels, rng, &constraints).expect("constraints should be satisfiable");
+
+    for level in &levels {
+        let behavior: u32 = level.behavior.into();
+        assert!(behavior == 1 || behavior == 4, "behavior {} not in {{1, 4}} required by objective level_type 9", behavior);
+    }
+}
+
+#[test]
+fn objective_level_type_constrains_behavior_to_idle() {
+    let mut levels = arbitrary_levels();
+    let constraints = constraints_from_json(r#"{"level_type": 17}"#);
+    let rng = Xoshiro256StarStar::seed_from_u64(0);
+
+    randomize(&mut levels, rng, &constraints).expect("constraints should be satisfiable");
+
+    for level in &levels {
+        let behavior: u32 = level.behavior.into();
+        assert_eq!(behavior, 0, "behavior required to be 0 by objective level_type 17");
+    }
+}
+
+#[test]
+fn forty_mii_map_caps_num_miis_at_forty() {
+    let mut levels = arbitrary_levels();
+    let constraints = constraints_from_json(r#"{"map": 4}"#);
+    let rng = Xoshiro256StarStar::seed_from_u64(0);
+
+    randomize(&mut levels, rng, &constraints).expect("constraints should be satisfiable");
+
+    for level in &levels {
+        assert!(level.num_miis <= 40, "num_miis {} exceeds the 40-mii cap for map 4", level.num_miis);
+    }
+}
+
+#[test]
+fn empty_set_constraint_is_a_clean_error_not_a_panic() {
+    let mut levels = arbitrary_levels();
+    let constraints = constraints_from_json(r#"{"level_type": []}"#);
+    let rng = Xoshiro256StarStar::seed_from_u64(0);
+
+    let result = randomize(&mut levels, rng, &constraints);
+    assert!(result.is_err(), "an empty set constraint must be reported as an error, not panic during sampling");
+}
+
+#[test]
+fn incompatible_min_max_constraint_is_a_clean_error() {
+    let mut levels = arbitrary_levels();
+    let constraints = constraints_from_json(r#"{"num_miis": {"min": 50, "max": 10}}"#);
+    let rng = Xoshiro256StarStar::seed_from_u64(0);
+
+    let result = randomize(&mut levels, rng, &constraints);
+    assert!(result.is_err(), "a min > max constraint must be reported as an error");
+}
@@ -0,0 +1,28 @@
+// Integration test for Level::from_file's short-read handling: a file that ends partway through
+// a level record must be reported as Truncated instead of panicking or silently dropping data.
+
+use std::io::{Seek, SeekFrom, Write};
+
+use find_mii_editor::level::{Level, LevelError};
+
+const RECORD_SIZE: usize = 64;
+
+#[test]
+fn from_file_reports_truncated_mid_record() {
+    let full_records = 2;
+    let partial_bytes = 10;
+
+    let mut file = tempfile::tempfile().expect("failed to create temporary file");
+    file.write_all(&vec![0u8; full_records * RECORD_SIZE]).expect("failed to write full records");
+    file.write_all(&vec![0u8; partial_bytes]).expect("failed to write partial record");
+    file.seek(SeekFrom::Start(0)).expect("failed to rewind temp file");
+
+    match Level::from_file(file) {
+        Err(LevelError::Truncated { level_index, byte_offset, bytes_read }) => {
+            assert_eq!(level_index, full_records, "truncation should be reported at the first incomplete record");
+            assert_eq!(byte_offset, (full_records * RECORD_SIZE) as u64, "byte offset should point at the start of the incomplete record");
+            assert_eq!(bytes_read, partial_bytes, "bytes_read should match how much of the final record was actually present");
+        },
+        other => panic!("expected LevelError::Truncated, got {:?}", other),
+    }
+}